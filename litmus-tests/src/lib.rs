@@ -1,6 +1,79 @@
 use core::ptr::write_volatile;
 use core::cell::UnsafeCell;
 use core::arch::asm;
+use core::ops::{Deref, DerefMut};
+
+/// Pads and aligns `T` to a 128-byte cache line, so it doesn't false-share
+/// with an adjacent `SharedMem`.
+#[repr(align(128))]
+pub struct CachePadded<T>(T);
+
+impl<T> CachePadded<T> {
+    pub fn new(val: T) -> Self {
+        CachePadded(val)
+    }
+}
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for CachePadded<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+// Upper bound on how many times `Backoff::spin` doubles its spin count
+// before falling back to yielding the OS thread.
+const SPIN_LIMIT: u32 = 6;
+
+/// Bounded spin-wait with exponential backoff, modeled on crossbeam-utils'
+/// `Backoff`.
+pub struct Backoff {
+    step: core::cell::Cell<u32>,
+}
+
+impl Backoff {
+    pub fn new() -> Self {
+        Backoff { step: core::cell::Cell::new(0) }
+    }
+
+    /// Busy-waits, doubling the spin count each call, then yields the OS
+    /// thread once `SPIN_LIMIT` has been reached.
+    pub fn spin(&self) {
+        if self.is_completed() {
+            self.snooze();
+            return;
+        }
+
+        for _ in 0..1u32 << self.step.get() {
+            core::hint::spin_loop();
+        }
+        self.step.set(self.step.get() + 1);
+    }
+
+    /// Yields the OS thread, for callers that want to skip straight past
+    /// the busy-wait phase.
+    pub fn snooze(&self) {
+        std::thread::yield_now();
+    }
+
+    /// Whether `spin()` has reached `SPIN_LIMIT` and is now just yielding.
+    pub fn is_completed(&self) -> bool {
+        self.step.get() > SPIN_LIMIT
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 // Simulates shared memory used for C <-> Rust communication.
 pub struct SharedMem(UnsafeCell<usize>);
@@ -16,6 +89,19 @@ impl SharedMem {
         unsafe { write_volatile(self.0.get(), val); }
     }
 
+    /// Spins with exponential backoff, reading via `read_once`, until
+    /// `pred` holds, then returns the value that satisfied it.
+    pub fn wait_until_once(&self, pred: impl Fn(usize) -> bool) -> usize {
+        let backoff = Backoff::new();
+        loop {
+            let val = self.read_once();
+            if pred(val) {
+                return val;
+            }
+            backoff.spin();
+        }
+    }
+
     /// Full barrier.
     ///
     /// C version: smp_mb()
@@ -29,19 +115,536 @@ impl SharedMem {
         // C version: asm volatile("dmbish": : : "memory");
         unsafe { asm!("mfence"); }
     }
+    #[cfg(target_arch = "riscv64")]
+    pub fn smp_mb() {
+        unsafe { asm!("fence rw, rw"); }
+    }
+    #[cfg(target_arch = "arm")]
+    pub fn smp_mb() {
+        unsafe { asm!("dmb ish"); }
+    }
+    #[cfg(target_arch = "powerpc64")]
+    pub fn smp_mb() {
+        unsafe { asm!("sync"); }
+    }
+    #[cfg(all(target_arch = "wasm32", target_feature = "atomics"))]
+    pub fn smp_mb() {
+        // Lowers to the `memory.atomic.fence` instruction from the wasm
+        // threads proposal.
+        core::sync::atomic::fence(core::sync::atomic::Ordering::SeqCst);
+    }
+    #[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+    pub fn smp_mb() {
+        // Single-threaded wasm has no cross-thread ordering to enforce, and
+        // `asm!` isn't supported on wasm32, so fall back to a compiler-only
+        // fence.
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+    #[cfg(not(any(
+        target_arch = "aarch64",
+        target_arch = "x86_64",
+        target_arch = "riscv64",
+        target_arch = "arm",
+        target_arch = "powerpc64",
+        target_arch = "wasm32",
+    )))]
+    compile_error!("SharedMem::smp_mb() has no backend for this target architecture");
+
+    /// Store with release ordering: orders all earlier accesses before the store.
+    ///
+    /// C version: smp_store_release()
+    #[cfg(target_arch = "aarch64")]
+    pub fn smp_store_release(&self, val: usize) {
+        unsafe { asm!("stlr {val:x}, [{ptr}]", val = in(reg) val, ptr = in(reg) self.0.get()); }
+    }
+    #[cfg(target_arch = "x86_64")]
+    pub fn smp_store_release(&self, val: usize) {
+        // x86_64 is TSO, so a plain store is already a release store; only a
+        // compiler barrier is needed to stop the store from being reordered
+        // by the compiler.
+        unsafe {
+            asm!("");
+            write_volatile(self.0.get(), val);
+        }
+    }
+    #[cfg(target_arch = "riscv64")]
+    pub fn smp_store_release(&self, val: usize) {
+        unsafe {
+            asm!("fence rw, w");
+            write_volatile(self.0.get(), val);
+        }
+    }
+    #[cfg(target_arch = "arm")]
+    pub fn smp_store_release(&self, val: usize) {
+        unsafe {
+            asm!("dmb ish");
+            write_volatile(self.0.get(), val);
+        }
+    }
+    #[cfg(target_arch = "powerpc64")]
+    pub fn smp_store_release(&self, val: usize) {
+        unsafe {
+            asm!("lwsync");
+            write_volatile(self.0.get(), val);
+        }
+    }
+    #[cfg(all(target_arch = "wasm32", target_feature = "atomics"))]
+    pub fn smp_store_release(&self, val: usize) {
+        core::sync::atomic::fence(core::sync::atomic::Ordering::Release);
+        unsafe { write_volatile(self.0.get(), val); }
+    }
+    #[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+    pub fn smp_store_release(&self, val: usize) {
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::Release);
+        unsafe { write_volatile(self.0.get(), val); }
+    }
+    #[cfg(not(any(
+        target_arch = "aarch64",
+        target_arch = "x86_64",
+        target_arch = "riscv64",
+        target_arch = "arm",
+        target_arch = "powerpc64",
+        target_arch = "wasm32",
+    )))]
+    pub fn smp_store_release(&self, _val: usize) {
+        compile_error!("SharedMem::smp_store_release() has no backend for this target architecture");
+    }
+
+    /// Load with acquire ordering: orders the load before all later accesses.
+    ///
+    /// C version: smp_load_acquire()
+    #[cfg(target_arch = "aarch64")]
+    pub fn smp_load_acquire(&self) -> usize {
+        let val: usize;
+        unsafe { asm!("ldar {val:x}, [{ptr}]", val = out(reg) val, ptr = in(reg) self.0.get()); }
+        val
+    }
+    #[cfg(target_arch = "x86_64")]
+    pub fn smp_load_acquire(&self) -> usize {
+        // x86_64 is TSO, so a plain load is already an acquire load; only a
+        // compiler barrier is needed to stop the load from being reordered
+        // by the compiler.
+        unsafe {
+            let val = self.0.get().read_volatile();
+            asm!("");
+            val
+        }
+    }
+    #[cfg(target_arch = "riscv64")]
+    pub fn smp_load_acquire(&self) -> usize {
+        unsafe {
+            let val = self.0.get().read_volatile();
+            asm!("fence r, rw");
+            val
+        }
+    }
+    #[cfg(target_arch = "arm")]
+    pub fn smp_load_acquire(&self) -> usize {
+        unsafe {
+            let val = self.0.get().read_volatile();
+            asm!("dmb ish");
+            val
+        }
+    }
+    #[cfg(target_arch = "powerpc64")]
+    pub fn smp_load_acquire(&self) -> usize {
+        unsafe {
+            let val = self.0.get().read_volatile();
+            asm!("lwsync");
+            val
+        }
+    }
+    #[cfg(all(target_arch = "wasm32", target_feature = "atomics"))]
+    pub fn smp_load_acquire(&self) -> usize {
+        let val = unsafe { self.0.get().read_volatile() };
+        core::sync::atomic::fence(core::sync::atomic::Ordering::Acquire);
+        val
+    }
+    #[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+    pub fn smp_load_acquire(&self) -> usize {
+        let val = unsafe { self.0.get().read_volatile() };
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::Acquire);
+        val
+    }
+    #[cfg(not(any(
+        target_arch = "aarch64",
+        target_arch = "x86_64",
+        target_arch = "riscv64",
+        target_arch = "arm",
+        target_arch = "powerpc64",
+        target_arch = "wasm32",
+    )))]
+    pub fn smp_load_acquire(&self) -> usize {
+        compile_error!("SharedMem::smp_load_acquire() has no backend for this target architecture");
+    }
 
     pub fn new(val: usize) -> Self {
         SharedMem(UnsafeCell::new(val))
     }
 }
 
+/// One-way read barrier: orders earlier reads before later reads.
+///
+/// C version: smp_rmb()
+#[cfg(target_arch = "aarch64")]
+pub fn smp_rmb() {
+    unsafe { asm!("dmb ishld"); }
+}
+#[cfg(target_arch = "x86_64")]
+pub fn smp_rmb() {
+    // x86_64 never reorders loads with later loads, so only a compiler
+    // barrier is needed.
+    unsafe { asm!(""); }
+}
+#[cfg(target_arch = "riscv64")]
+pub fn smp_rmb() {
+    unsafe { asm!("fence r, r"); }
+}
+#[cfg(target_arch = "arm")]
+pub fn smp_rmb() {
+    unsafe { asm!("dmb ish"); }
+}
+#[cfg(target_arch = "powerpc64")]
+pub fn smp_rmb() {
+    unsafe { asm!("lwsync"); }
+}
+#[cfg(all(target_arch = "wasm32", target_feature = "atomics"))]
+pub fn smp_rmb() {
+    core::sync::atomic::fence(core::sync::atomic::Ordering::Acquire);
+}
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+pub fn smp_rmb() {
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::Acquire);
+}
+#[cfg(not(any(
+    target_arch = "aarch64",
+    target_arch = "x86_64",
+    target_arch = "riscv64",
+    target_arch = "arm",
+    target_arch = "powerpc64",
+    target_arch = "wasm32",
+)))]
+pub fn smp_rmb() {
+    compile_error!("smp_rmb() has no backend for this target architecture");
+}
+
+/// One-way write barrier: orders earlier writes before later writes.
+///
+/// C version: smp_wmb()
+#[cfg(target_arch = "aarch64")]
+pub fn smp_wmb() {
+    unsafe { asm!("dmb ishst"); }
+}
+#[cfg(target_arch = "x86_64")]
+pub fn smp_wmb() {
+    // x86_64 never reorders writes with later writes, so only a compiler
+    // barrier is needed.
+    unsafe { asm!(""); }
+}
+#[cfg(target_arch = "riscv64")]
+pub fn smp_wmb() {
+    unsafe { asm!("fence w, w"); }
+}
+#[cfg(target_arch = "arm")]
+pub fn smp_wmb() {
+    unsafe { asm!("dmb ish"); }
+}
+#[cfg(target_arch = "powerpc64")]
+pub fn smp_wmb() {
+    unsafe { asm!("lwsync"); }
+}
+#[cfg(all(target_arch = "wasm32", target_feature = "atomics"))]
+pub fn smp_wmb() {
+    core::sync::atomic::fence(core::sync::atomic::Ordering::Release);
+}
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+pub fn smp_wmb() {
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::Release);
+}
+#[cfg(not(any(
+    target_arch = "aarch64",
+    target_arch = "x86_64",
+    target_arch = "riscv64",
+    target_arch = "arm",
+    target_arch = "powerpc64",
+    target_arch = "wasm32",
+)))]
+pub fn smp_wmb() {
+    compile_error!("smp_wmb() has no backend for this target architecture");
+}
+
 
 // According to LKMM, read_once and write_once are volatile atomic.
 unsafe impl Sync for SharedMem {}
 
+/// LKMM ordering of a read-modify-write atomic operation.
+///
+/// C version: smp_mb__before_atomic() / smp_mb__after_atomic()
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RmwOrdering {
+    Relaxed,
+    Acquire,
+    Release,
+    Mb,
+}
+
+// Simulates a C `atomic_t` used for RMW litmus tests.
+pub struct LkmmAtomic(UnsafeCell<usize>);
+
+impl LkmmAtomic {
+    pub fn new(val: usize) -> Self {
+        LkmmAtomic(UnsafeCell::new(val))
+    }
+
+    /// Add `val`, returning the previous value.
+    ///
+    /// C version: atomic_fetch_add()
+    #[cfg(target_arch = "aarch64")]
+    pub fn fetch_add(&self, val: usize, order: RmwOrdering) -> usize {
+        self.rmw(order, |old| old.wrapping_add(val))
+    }
+    #[cfg(target_arch = "x86_64")]
+    pub fn fetch_add(&self, val: usize, _order: RmwOrdering) -> usize {
+        // `lock xadd` is already a full barrier, so every LKMM ordering maps
+        // onto the same wait-free instruction.
+        let ptr = self.0.get();
+        let mut prev = val;
+        unsafe {
+            asm!("lock xadd [{ptr}], {prev}", ptr = in(reg) ptr, prev = inout(reg) prev);
+        }
+        prev
+    }
+
+    /// Add `val`, returning the new value.
+    ///
+    /// C version: atomic_add_return()
+    pub fn add_return(&self, val: usize, order: RmwOrdering) -> usize {
+        self.fetch_add(val, order).wrapping_add(val)
+    }
+
+    /// Exchange `val` in, returning the previous value.
+    ///
+    /// C version: xchg()
+    #[cfg(target_arch = "aarch64")]
+    pub fn xchg(&self, val: usize, order: RmwOrdering) -> usize {
+        self.rmw(order, |_old| val)
+    }
+    #[cfg(target_arch = "x86_64")]
+    pub fn xchg(&self, val: usize, _order: RmwOrdering) -> usize {
+        // A memory-operand `xchg` is implicitly locked and already a full
+        // barrier, so every LKMM ordering maps onto the same wait-free
+        // instruction.
+        let ptr = self.0.get();
+        let mut prev = val;
+        unsafe {
+            asm!("xchg [{ptr}], {prev}", ptr = in(reg) ptr, prev = inout(reg) prev);
+        }
+        prev
+    }
+
+    /// Exchange `new` in if the current value is `old`, returning the value
+    /// observed before the exchange either way.
+    ///
+    /// C version: cmpxchg()
+    #[cfg(target_arch = "aarch64")]
+    pub fn cmpxchg(&self, old: usize, new: usize, order: RmwOrdering) -> usize {
+        self.rmw(order, |cur| if cur == old { new } else { cur })
+    }
+    #[cfg(target_arch = "x86_64")]
+    pub fn cmpxchg(&self, old: usize, new: usize, _order: RmwOrdering) -> usize {
+        // `lock cmpxchg` is already a full barrier, and a single attempt
+        // already has the right semantics: it compares, swaps if equal, and
+        // either way returns the value observed before the attempt.
+        let ptr = self.0.get();
+        let mut prev = old;
+        unsafe {
+            asm!(
+                "lock cmpxchg [{ptr}], {new}",
+                ptr = in(reg) ptr,
+                new = in(reg) new,
+                inout("rax") prev,
+            );
+        }
+        prev
+    }
+
+    // LL/SC loop: ldxr/ldaxr select the acquire suffix, stxr/stlxr select
+    // the release suffix, and `Mb` additionally gets a trailing full
+    // barrier so it orders accesses on both sides of the RMW.
+    #[cfg(target_arch = "aarch64")]
+    fn rmw(&self, order: RmwOrdering, f: impl Fn(usize) -> usize) -> usize {
+        let ptr = self.0.get();
+        loop {
+            let old: usize;
+            unsafe {
+                match order {
+                    RmwOrdering::Relaxed | RmwOrdering::Release => {
+                        asm!("ldxr {old:x}, [{ptr}]", old = out(reg) old, ptr = in(reg) ptr);
+                    }
+                    RmwOrdering::Acquire | RmwOrdering::Mb => {
+                        asm!("ldaxr {old:x}, [{ptr}]", old = out(reg) old, ptr = in(reg) ptr);
+                    }
+                }
+            }
+
+            let new = f(old);
+
+            let status: usize;
+            unsafe {
+                match order {
+                    RmwOrdering::Relaxed | RmwOrdering::Acquire => {
+                        asm!("stxr {status:w}, {new:x}, [{ptr}]", status = out(reg) status, new = in(reg) new, ptr = in(reg) ptr);
+                    }
+                    RmwOrdering::Release | RmwOrdering::Mb => {
+                        asm!("stlxr {status:w}, {new:x}, [{ptr}]", status = out(reg) status, new = in(reg) new, ptr = in(reg) ptr);
+                    }
+                }
+            }
+
+            if status == 0 {
+                if order == RmwOrdering::Mb {
+                    unsafe { asm!("dmb ish"); }
+                }
+                return old;
+            }
+        }
+    }
+
+    #[cfg(not(any(target_arch = "aarch64", target_arch = "x86_64")))]
+    pub fn fetch_add(&self, _val: usize, _order: RmwOrdering) -> usize {
+        compile_error!("LkmmAtomic::fetch_add() has no backend for this target architecture");
+    }
+    #[cfg(not(any(target_arch = "aarch64", target_arch = "x86_64")))]
+    pub fn xchg(&self, _val: usize, _order: RmwOrdering) -> usize {
+        compile_error!("LkmmAtomic::xchg() has no backend for this target architecture");
+    }
+    #[cfg(not(any(target_arch = "aarch64", target_arch = "x86_64")))]
+    pub fn cmpxchg(&self, _old: usize, _new: usize, _order: RmwOrdering) -> usize {
+        compile_error!("LkmmAtomic::cmpxchg() has no backend for this target architecture");
+    }
+}
+
+unsafe impl Sync for LkmmAtomic {}
+
+/// A reusable litmus-test harness: runs the same threads over and over
+/// against freshly initialized shared state, rendezvousing them on a
+/// shared barrier before every iteration (the same idea as
+/// crossbeam-utils' `WaitGroup`).
+pub mod litmus {
+    use std::cell::UnsafeCell;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    /// Default iteration count: enough for weak-memory effects to show up
+    /// on typical aarch64 hardware without making the test suite slow.
+    pub const DEFAULT_ITERATIONS: usize = 100_000;
+
+    /// One run's per-thread local register values, in thread registration
+    /// order.
+    pub type Outcome = Vec<Vec<usize>>;
+
+    struct Slot<S> {
+        state: UnsafeCell<Option<S>>,
+        results: Vec<UnsafeCell<Vec<usize>>>,
+    }
+
+    // Access to `state` and `results` is synchronized by `start`/`done`
+    // barriers in `Litmus::run`, not by any lock.
+    unsafe impl<S: Send> Sync for Slot<S> {}
+
+    type ThreadBody<S> = Box<dyn Fn(&S) -> Vec<usize> + Send + Sync>;
+
+    /// Declares a litmus test: fresh shared state plus one closure per
+    /// thread returning that thread's local register values.
+    pub struct Litmus<S> {
+        init: Box<dyn Fn() -> S + Send + Sync>,
+        threads: Vec<ThreadBody<S>>,
+    }
+
+    impl<S: Send + Sync + 'static> Litmus<S> {
+        pub fn new(init: impl Fn() -> S + Send + Sync + 'static) -> Self {
+            Litmus { init: Box::new(init), threads: Vec::new() }
+        }
+
+        /// Registers one more thread's body. Threads are numbered in the
+        /// order they're added here, and that's the order their register
+        /// values appear in each `Outcome`.
+        pub fn thread(mut self, body: impl Fn(&S) -> Vec<usize> + Send + Sync + 'static) -> Self {
+            self.threads.push(Box::new(body));
+            self
+        }
+
+        /// Runs `iterations` rounds and returns a histogram of how often
+        /// each distinct outcome occurred.
+        pub fn run(&self, iterations: usize) -> Vec<(Outcome, u64)> {
+            let nthreads = self.threads.len();
+            let slot = Arc::new(Slot {
+                state: UnsafeCell::new(None),
+                results: (0..nthreads).map(|_| UnsafeCell::new(Vec::new())).collect(),
+            });
+            // `start` releases the workers into one iteration together;
+            // `done` holds the driver until they've all recorded a result.
+            let start = Arc::new(Barrier::new(nthreads + 1));
+            let done = Arc::new(Barrier::new(nthreads + 1));
+
+            thread::scope(|scope| {
+                for (i, body) in self.threads.iter().enumerate() {
+                    let slot = Arc::clone(&slot);
+                    let start = Arc::clone(&start);
+                    let done = Arc::clone(&done);
+
+                    scope.spawn(move || {
+                        for _ in 0..iterations {
+                            start.wait();
+
+                            let state = unsafe { (*slot.state.get()).as_ref().unwrap() };
+                            let result = body(state);
+                            unsafe { *slot.results[i].get() = result; }
+
+                            done.wait();
+                        }
+                    });
+                }
+
+                let mut counts: HashMap<Outcome, u64> = HashMap::new();
+                for _ in 0..iterations {
+                    unsafe { *slot.state.get() = Some((self.init)()); }
+
+                    start.wait();
+                    done.wait();
+
+                    let outcome: Outcome =
+                        slot.results.iter().map(|r| unsafe { (*r.get()).clone() }).collect();
+                    *counts.entry(outcome).or_insert(0) += 1;
+                }
+
+                counts.into_iter().collect()
+            })
+        }
+
+        /// Runs the harness and asserts that no outcome matching `exists`
+        /// is ever observed, the way a C litmus tool reports `Never`.
+        pub fn assert_never(
+            &self,
+            iterations: usize,
+            exists: impl Fn(&Outcome) -> bool,
+        ) -> Vec<(Outcome, u64)> {
+            let histogram = self.run(iterations);
+            let forbidden: u64 = histogram
+                .iter()
+                .filter(|(outcome, _)| exists(outcome))
+                .map(|(_, count)| *count)
+                .sum();
+            assert_eq!(forbidden, 0, "forbidden outcome observed {forbidden} time(s)");
+            histogram
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::thread;
+    use super::litmus::{Litmus, DEFAULT_ITERATIONS};
     use super::*;
 
     #[test]
@@ -49,31 +652,21 @@ mod tests {
         // C litmus test:
         // tools/memory-model/litmus-tests/CoRR+poonceonce+Once.litmus
 
-        let x_in_mem = SharedMem::new(0);
-
-        thread::scope(|scope| {
-            let x = &x_in_mem;
-
-            let p0 = scope.spawn(move || {
+        Litmus::new(|| SharedMem::new(0))
+            .thread(|x| {
                 x.write_once(1);
-            });
-
-            let p1 = scope.spawn(move || -> (usize, usize) {
+                vec![]
+            })
+            .thread(|x| {
                 let r0 = x.read_once();
                 let r1 = x.read_once();
-
-                (r0, r1)
+                vec![r0, r1]
+            })
+            .assert_never(DEFAULT_ITERATIONS, |outcome| {
+                // exists (1:r0=1 /\ 1:r1=0)
+                let (r0, r1) = (outcome[1][0], outcome[1][1]);
+                r0 == 1 && r1 == 0
             });
-
-            p0.join().unwrap();
-            let (r0, r1) = p1.join().unwrap();
-
-            // Result: Never
-            // exists (1:r0=1 /\ 1:r1=0)
-            //
-            // expect r0 == 1 && r1 == 0 never happens
-            assert!(!(r0 == 1 && r1 == 0));
-        });
     }
 
     #[test]
@@ -81,41 +674,77 @@ mod tests {
         // C litmus test:
         // tools/memory-model/litmus-tests/LB+fencembonceonce+ctrlonceonce.litmus
 
-        let x_in_mem = SharedMem::new(0);
-        let y_in_mem = SharedMem::new(0);
-
-        thread::scope(|scope| {
-            let x = &x_in_mem;
-            let y = &y_in_mem;
-
-            let p0 = scope.spawn(move || -> usize {
+        // x and y are padded onto separate cache lines so false sharing
+        // between them doesn't mask the reordering this test looks for.
+        Litmus::new(|| (CachePadded::new(SharedMem::new(0)), CachePadded::new(SharedMem::new(0))))
+            .thread(|(x, y)| {
                 let r0 = x.read_once();
 
                 if r0 != 0 {
                     y.write_once(1);
                 }
 
-                r0
-            });
-
-            let p1 = scope.spawn(move || -> usize {
+                vec![r0]
+            })
+            .thread(|(x, y)| {
                 let r0 = y.read_once();
 
                 SharedMem::smp_mb();
                 x.write_once(1);
 
-                r0
+                vec![r0]
+            })
+            .assert_never(DEFAULT_ITERATIONS, |outcome| {
+                // exists (0:r0=1 /\ 1:r0=1)
+                outcome[0][0] == 1 && outcome[1][0] == 1
             });
+    }
 
-            let p0_r0 = p0.join().unwrap();
-            let p1_r0 = p1.join().unwrap();
+    #[test]
+    fn mp_pooncerelease_poacquireonce() {
+        // C litmus test:
+        // tools/memory-model/litmus-tests/MP+pooncerelease+poacquireonce.litmus
 
-            // Result: Never
-            // exists (0:r0=1 /\ 1:r0=1)
-            //
-            // expect p0_r0 == 1 && p1_r0 == 0 never happens
-            assert!(!(p0_r0 == 1 && p1_r0 == 0));
-        });
+        Litmus::new(|| (SharedMem::new(0), SharedMem::new(0)))
+            .thread(|(x, y)| {
+                x.write_once(1);
+                y.smp_store_release(1);
+                vec![]
+            })
+            .thread(|(x, y)| {
+                let r0 = y.smp_load_acquire();
+                let r1 = x.read_once();
+                vec![r0, r1]
+            })
+            .assert_never(DEFAULT_ITERATIONS, |outcome| {
+                // exists (1:r0=1 /\ 1:r1=0)
+                outcome[1][0] == 1 && outcome[1][1] == 0
+            });
     }
 
+    #[test]
+    fn sb_atomicmbonceonces() {
+        // C litmus test:
+        // tools/memory-model/litmus-tests/SB+mbonceonces.litmus, with both
+        // writes and both reads done through LkmmAtomic instead of
+        // WRITE_ONCE()/READ_ONCE()+smp_mb(), to exercise atomic_xchg() with a
+        // full barrier (RmwOrdering::Mb) and atomic_fetch_add_acquire()
+        // (RmwOrdering::Acquire).
+
+        Litmus::new(|| (LkmmAtomic::new(0), LkmmAtomic::new(0)))
+            .thread(|(x, y)| {
+                x.xchg(1, RmwOrdering::Mb);
+                let r0 = y.fetch_add(0, RmwOrdering::Acquire);
+                vec![r0]
+            })
+            .thread(|(x, y)| {
+                y.xchg(1, RmwOrdering::Mb);
+                let r0 = x.fetch_add(0, RmwOrdering::Acquire);
+                vec![r0]
+            })
+            .assert_never(DEFAULT_ITERATIONS, |outcome| {
+                // exists (0:r0=0 /\ 1:r0=0)
+                outcome[0][0] == 0 && outcome[1][0] == 0
+            });
+    }
 }